@@ -1,9 +1,10 @@
 use std::collections::HashMap;
+use std::fmt;
+use std::io::{Error, ErrorKind};
 
 use lazy_static::lazy_static;
 
-use crate::token::{Literal, Token, TokenType};
-use crate::utils;
+use crate::token::{Literal, Position, Token, TokenType};
 
 lazy_static! {
     static ref KEYWORDS: HashMap<&'static str, TokenType> = {
@@ -30,132 +31,223 @@ lazy_static! {
     };
 }
 
+/// A lexical error produced while scanning a source string. Scanning keeps
+/// going after one of these so that a single pass can surface every problem
+/// in the source rather than stopping at the first.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScannerError {
+    UnexpectedChar { line: u32, ch: char },
+    UnterminatedString { line: u32 },
+    InvalidNumber { line: u32, text: String },
+    UnterminatedComment { line: u32 },
+    InvalidEscape { line: u32, seq: String },
+}
+
+impl fmt::Display for ScannerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScannerError::UnexpectedChar { line, ch } => {
+                write!(f, "[line {}] Error: Unexpected character '{}'.", line, ch)
+            }
+            ScannerError::UnterminatedString { line } => {
+                write!(f, "[line {}] Error: Unterminated string.", line)
+            }
+            ScannerError::InvalidNumber { line, text } => {
+                write!(
+                    f,
+                    "[line {}] Error: Invalid number literal '{}'.",
+                    line, text
+                )
+            }
+            ScannerError::UnterminatedComment { line } => {
+                write!(f, "[line {}] Error: Unterminated block comment.", line)
+            }
+            ScannerError::InvalidEscape { line, seq } => {
+                write!(
+                    f,
+                    "[line {}] Error: Invalid escape sequence '\\{}'.",
+                    line, seq
+                )
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Scanner {
     chars: Vec<char>,
-    tokens: Vec<Token>,
+    errors: Vec<ScannerError>,
+    exhausted: bool,
     start: usize,
     current: usize,
     line: u32,
+    col: u32,
+    start_line: u32,
+    start_col: u32,
 }
 
 impl Scanner {
     pub fn new(source: &str) -> Scanner {
         Scanner {
             chars: source.chars().collect(),
-            tokens: Vec::<Token>::new(),
+            errors: Vec::<ScannerError>::new(),
+            exhausted: false,
             start: 0,
             current: 0,
             line: 1,
+            col: 1,
+            start_line: 1,
+            start_col: 1,
         }
     }
 
-    pub fn scan_tokens(&mut self) -> Vec<Token> {
-        while !self.is_at_end() {
-            self.start = self.current;
+    /// Drains the iterator into a `Vec`, keeping the errors gathered along
+    /// the way on `self.errors` for the final `Result`.
+    pub fn scan_tokens(&mut self) -> Result<Vec<Token>, Vec<ScannerError>> {
+        let tokens: Vec<Token> = self.collect();
 
-            self.scan_token();
+        if self.errors.is_empty() {
+            Ok(tokens)
+        } else {
+            Err(std::mem::take(&mut self.errors))
         }
+    }
+
+    /// Produces the next token, emitting `EOF` once the source is exhausted.
+    pub fn next_token(&mut self) -> Result<Token, ScannerError> {
+        loop {
+            self.start = self.current;
+            self.start_line = self.line;
+            self.start_col = self.col;
 
-        self.tokens
-            .push(Token::new(TokenType::EOF, "".to_string(), None, self.line));
+            if self.is_at_end() {
+                return Ok(self.make_token(TokenType::EOF));
+            }
 
-        self.tokens.iter().cloned().collect()
+            if let Some(result) = self.scan_token() {
+                return result;
+            }
+        }
     }
 
     fn is_at_end(&self) -> bool {
         return self.current >= self.chars.len();
     }
 
-    fn scan_token(&mut self) {
+    /// Scans a single token starting at `self.start`, returning `None` for
+    /// trivia (whitespace, comments) that doesn't produce a token.
+    fn scan_token(&mut self) -> Option<Result<Token, ScannerError>> {
         match self.advance() {
-            '(' => self.add_token(TokenType::LeftParen),
-            ')' => self.add_token(TokenType::RightParen),
-            '{' => self.add_token(TokenType::LeftParen),
-            '}' => self.add_token(TokenType::RightBrace),
-            ',' => self.add_token(TokenType::Dot),
-            '.' => self.add_token(TokenType::Comma),
-            '-' => self.add_token(TokenType::Minus),
-            '+' => self.add_token(TokenType::Plus),
-            ';' => self.add_token(TokenType::Semicolon),
-            '*' => self.add_token(TokenType::Plus),
+            '(' => Some(Ok(self.make_token(TokenType::LeftParen))),
+            ')' => Some(Ok(self.make_token(TokenType::RightParen))),
+            '{' => Some(Ok(self.make_token(TokenType::LeftBrace))),
+            '}' => Some(Ok(self.make_token(TokenType::RightBrace))),
+            ',' => Some(Ok(self.make_token(TokenType::Comma))),
+            '.' => Some(Ok(self.make_token(TokenType::Dot))),
+            '-' => Some(Ok(self.make_token(TokenType::Minus))),
+            '+' => Some(Ok(self.make_token(TokenType::Plus))),
+            ';' => Some(Ok(self.make_token(TokenType::Semicolon))),
+            '*' => Some(Ok(self.make_token(TokenType::Star))),
             // Operators
             '!' => {
-                if self.next_if_eq('=') {
-                    self.add_token(TokenType::BangEqual)
+                let kind = if self.next_if_eq('=') {
+                    TokenType::BangEqual
                 } else {
-                    self.add_token(TokenType::Bang)
-                }
+                    TokenType::Bang
+                };
+                Some(Ok(self.make_token(kind)))
             }
             '=' => {
-                if self.next_if_eq('=') {
-                    self.add_token(TokenType::EqualEqual);
+                let kind = if self.next_if_eq('=') {
+                    TokenType::EqualEqual
                 } else {
-                    self.add_token(TokenType::Equal);
-                }
+                    TokenType::Equal
+                };
+                Some(Ok(self.make_token(kind)))
             }
             '<' => {
-                if self.next_if_eq('=') {
-                    self.add_token(TokenType::LessEqual)
+                let kind = if self.next_if_eq('=') {
+                    TokenType::LessEqual
                 } else {
-                    self.add_token(TokenType::Less)
-                }
+                    TokenType::Less
+                };
+                Some(Ok(self.make_token(kind)))
             }
             '>' => {
-                if self.next_if_eq('=') {
-                    self.add_token(TokenType::GreaterEqual)
+                let kind = if self.next_if_eq('=') {
+                    TokenType::GreaterEqual
                 } else {
-                    self.add_token(TokenType::Greater)
-                }
+                    TokenType::Greater
+                };
+                Some(Ok(self.make_token(kind)))
             }
             '/' => {
                 if self.next_if_eq('/') {
-                    while self.peek() != Some('\n') {
+                    while !matches!(self.peek(), None | Some('\n')) {
                         self.advance();
                     }
+                    None
+                } else if self.next_if_eq('*') {
+                    match self.block_comment() {
+                        Ok(()) => None,
+                        Err(err) => Some(Err(err)),
+                    }
                 } else {
-                    self.add_token(TokenType::Slash)
+                    Some(Ok(self.make_token(TokenType::Slash)))
                 }
             }
-            ' ' | '\r' | '\t' => {}
+            ' ' | '\r' | '\t' => None,
             '\n' => {
                 self.line += 1;
+                None
             }
-            '"' => self.string(),
-            c if c.is_ascii_digit() => {
-                self.number();
-            }
-            c if c.is_alphabetic() => {
-                self.identifier();
-            }
-            _ => {
-                utils::error(self.line, "Unexpected characters.");
-            }
+            '"' => Some(self.string()),
+            c if c.is_ascii_digit() => Some(self.number()),
+            c if c.is_alphabetic() => Some(Ok(self.identifier())),
+            ch => Some(Err(ScannerError::UnexpectedChar {
+                line: self.line,
+                ch,
+            })),
         }
     }
 
     fn advance(&mut self) -> char {
         let ch = self.chars[self.current];
         self.current += 1;
+        if ch == '\n' {
+            self.col = 1;
+        } else {
+            self.col += 1;
+        }
         ch
     }
 
-    fn add_token(&mut self, kind: TokenType) {
-        self.tokens.push(Token::new(
+    fn position(&self) -> Position {
+        Position {
+            line: self.start_line,
+            col_start: self.start_col,
+            col_end: self.col,
+            span_start: self.start,
+            span_end: self.current,
+        }
+    }
+
+    fn make_token(&self, kind: TokenType) -> Token {
+        Token::new(
             kind,
             substr(&self.chars, self.start, self.current),
             None,
-            self.line,
-        ))
+            self.position(),
+        )
     }
 
-    fn add_literal(&mut self, kind: TokenType, literal: Option<Literal>) {
-        self.tokens.push(Token::new(
+    fn make_literal(&self, kind: TokenType, literal: Option<Literal>) -> Token {
+        Token::new(
             kind,
             substr(&self.chars, self.start, self.current),
             literal,
-            self.line,
-        ))
+            self.position(),
+        )
     }
 
     fn next_if_eq(&mut self, expected: char) -> bool {
@@ -167,6 +259,7 @@ impl Scanner {
         }
 
         self.current += 1;
+        self.col += 1;
         true
     }
 
@@ -180,57 +273,299 @@ impl Scanner {
 
     fn peek_next(&self) -> Option<char> {
         let idx = self.current + 1;
-        if idx + 1 >= self.chars.len() {
+        if idx >= self.chars.len() {
             None
         } else {
             Some(self.chars[idx])
         }
     }
 
-    fn string(&mut self) {
-        while let Some(ch) = self.peek() {
-            if ch == '"' {
-                break;
-            }
-            if ch == '\n' {
-                self.line += 1;
+    /// Consumes a `/* ... */` block comment, having already consumed the
+    /// opening `/*`. Nested `/*...*/` pairs are tracked via `depth` so an
+    /// inner comment doesn't end the outer one early.
+    fn block_comment(&mut self) -> Result<(), ScannerError> {
+        let opening_line = self.line;
+        let mut depth = 1;
+
+        while depth > 0 {
+            match self.peek() {
+                None => return Err(ScannerError::UnterminatedComment { line: opening_line }),
+                Some('\n') => {
+                    self.advance();
+                    self.line += 1;
+                }
+                Some('/') if self.peek_next() == Some('*') => {
+                    self.advance();
+                    self.advance();
+                    depth += 1;
+                }
+                Some('*') if self.peek_next() == Some('/') => {
+                    self.advance();
+                    self.advance();
+                    depth -= 1;
+                }
+                Some(_) => {
+                    self.advance();
+                }
             }
-            self.advance();
         }
 
-        if self.is_at_end() {
-            utils::error(self.line, "Unterminated string.");
-            return;
+        Ok(())
+    }
+
+    /// Scans a string literal, decoding escape sequences as it goes rather
+    /// than slicing the raw source verbatim.
+    ///
+    /// A bad escape doesn't stop the scan early (the string is still well-
+    /// formed source, so we keep reading all the way to the real closing
+    /// quote), but it does make the call report `Err` once that quote is
+    /// reached — the same `Result<Token, ScannerError>` channel every other
+    /// error goes through, so `next_token` callers see it directly instead
+    /// of having to know to check a private field afterwards.
+    fn string(&mut self) -> Result<Token, ScannerError> {
+        let mut value = String::new();
+        let mut first_error = None;
+
+        loop {
+            match self.peek() {
+                None => return Err(ScannerError::UnterminatedString { line: self.line }),
+                Some('"') => break,
+                Some('\\') => {
+                    self.advance();
+
+                    match self.escape() {
+                        Ok(ch) => value.push(ch),
+                        Err(err @ ScannerError::UnterminatedString { .. }) => return Err(err),
+                        Err(err) => {
+                            first_error.get_or_insert(err);
+                            value.push('\u{FFFD}');
+                        }
+                    }
+                }
+                Some(ch) => {
+                    if ch == '\n' {
+                        self.line += 1;
+                    }
+                    self.advance();
+                    value.push(ch);
+                }
+            }
         }
 
-        self.advance();
+        self.advance(); // closing quote
 
-        let value: String = substr(&self.chars, self.start + 1, self.current - 1);
+        match first_error {
+            Some(err) => Err(err),
+            None => Ok(self.make_literal(TokenType::String, Some(Literal::String(value)))),
+        }
+    }
+
+    /// Decodes one escape sequence, having already consumed the `\`.
+    fn escape(&mut self) -> Result<char, ScannerError> {
+        let line = self.line;
 
-        self.add_literal(TokenType::String, Some(Literal::String(value)));
+        match self.peek() {
+            Some('n') => {
+                self.advance();
+                Ok('\n')
+            }
+            Some('t') => {
+                self.advance();
+                Ok('\t')
+            }
+            Some('r') => {
+                self.advance();
+                Ok('\r')
+            }
+            Some('\\') => {
+                self.advance();
+                Ok('\\')
+            }
+            Some('"') => {
+                self.advance();
+                Ok('"')
+            }
+            Some('0') => {
+                self.advance();
+                Ok('\0')
+            }
+            Some('u') => self.unicode_escape(),
+            Some(other) => Err(ScannerError::InvalidEscape {
+                line,
+                seq: other.to_string(),
+            }),
+            None => Err(ScannerError::UnterminatedString { line }),
+        }
     }
 
-    fn number(&mut self) {
-        while self.peek().map_or(false, |c| c.is_ascii_digit()) {
+    /// Decodes a `\u{XXXX}` escape, having already consumed the `\`.
+    fn unicode_escape(&mut self) -> Result<char, ScannerError> {
+        let line = self.line;
+        self.advance(); // 'u'
+
+        if self.peek() != Some('{') {
+            return Err(ScannerError::InvalidEscape {
+                line,
+                seq: "u".to_string(),
+            });
+        }
+        self.advance(); // '{'
+
+        let digits_start = self.current;
+        while self.peek().map_or(false, |c| c.is_ascii_hexdigit()) {
             self.advance();
         }
+        let digits = substr(&self.chars, digits_start, self.current);
 
-        if self.peek() == Some('.') && self.peek_next().map_or(false, |c| c.is_ascii_digit()) {
+        if self.peek() != Some('}') {
+            return Err(ScannerError::InvalidEscape {
+                line,
+                seq: format!("u{{{}", digits),
+            });
+        }
+        self.advance(); // '}'
+
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| ScannerError::InvalidEscape {
+                line,
+                seq: format!("u{{{}}}", digits),
+            })
+    }
+
+    fn number(&mut self) -> Result<Token, ScannerError> {
+        if self.chars[self.start] == '0' {
+            match self.peek() {
+                Some('x') | Some('X') => return self.radix_number(16),
+                Some('b') | Some('B') => return self.radix_number(2),
+                Some('o') | Some('O') => return self.radix_number(8),
+                _ => {}
+            }
+        }
+
+        self.decimal_number()
+    }
+
+    /// Scans the digits of a `0x`/`0b`/`0o`-prefixed integer, having already
+    /// consumed the leading `0` but not the base letter.
+    fn radix_number(&mut self, base: u32) -> Result<Token, ScannerError> {
+        self.advance(); // the base letter (x/b/o)
+
+        let digits_start = self.current;
+
+        while self
+            .peek()
+            .map_or(false, |c| Self::is_in_base(c, base) || c == '_')
+        {
             self.advance();
+        }
 
-            while self.peek().map_or(false, |c| c.is_ascii_digit()) {
+        let digits: String = substr(&self.chars, digits_start, self.current)
+            .chars()
+            .filter(|&c| c != '_')
+            .collect();
+
+        // A digit out of range for this base (e.g. the `9` in `0b19`, or
+        // every digit in `0xzz` once none of them fit the base) isn't part
+        // of the literal per the loop above, but it's glued onto it rather
+        // than starting a new token, so consume it too and fold it into a
+        // single error instead of leaving it to be scanned as its own
+        // token. This has to happen before the `digits.is_empty()` check
+        // below, since an entirely out-of-range suffix (`0xzz`) leaves
+        // `digits` empty but still needs to be swallowed.
+        let has_glued_suffix = self
+            .peek()
+            .map_or(false, |c| c.is_alphanumeric() || c == '_');
+        if has_glued_suffix {
+            while self
+                .peek()
+                .map_or(false, |c| c.is_alphanumeric() || c == '_')
+            {
                 self.advance();
             }
         }
 
-        let value = substr(&self.chars, self.start, self.current)
-            .parse::<f64>()
-            .unwrap_or_default();
+        if digits.is_empty() || has_glued_suffix {
+            return Err(self.invalid_number());
+        }
 
-        self.add_literal(TokenType::Number, Some(Literal::Number(value)))
+        match u64::from_str_radix(&digits, base) {
+            Ok(value) => {
+                Ok(self.make_literal(TokenType::Number, Some(Literal::Number(value as f64))))
+            }
+            Err(_) => Err(self.invalid_number()),
+        }
     }
 
-    fn identifier(&mut self) {
+    /// Scans a decimal literal, allowing `_` digit-group separators.
+    fn decimal_number(&mut self) -> Result<Token, ScannerError> {
+        self.consume_digit_group();
+
+        if self.peek() == Some('.') && self.peek_next().map_or(false, |c| c.is_ascii_digit()) {
+            self.advance();
+            self.consume_digit_group();
+        }
+
+        let raw = substr(&self.chars, self.start, self.current);
+
+        if !Self::has_valid_underscores(&raw) {
+            return Err(self.invalid_number());
+        }
+
+        let digits: String = raw.chars().filter(|&c| c != '_').collect();
+        let value = digits.parse::<f64>().unwrap_or_default();
+
+        Ok(self.make_literal(TokenType::Number, Some(Literal::Number(value))))
+    }
+
+    fn consume_digit_group(&mut self) {
+        while self
+            .peek()
+            .map_or(false, |c| c.is_ascii_digit() || c == '_')
+        {
+            self.advance();
+        }
+    }
+
+    fn invalid_number(&self) -> ScannerError {
+        ScannerError::InvalidNumber {
+            line: self.line,
+            text: substr(&self.chars, self.start, self.current),
+        }
+    }
+
+    fn is_in_base(ch: char, base: u32) -> bool {
+        match base {
+            2 => matches!(ch, '0'..='1'),
+            8 => matches!(ch, '0'..='7'),
+            16 => matches!(ch, '0'..='9' | 'a'..='f' | 'A'..='F'),
+            _ => ch.is_ascii_digit(),
+        }
+    }
+
+    /// Rejects leading/trailing/doubled `_` separators and ones touching `.`.
+    fn has_valid_underscores(text: &str) -> bool {
+        let chars: Vec<char> = text.chars().collect();
+
+        for (i, &c) in chars.iter().enumerate() {
+            if c != '_' {
+                continue;
+            }
+
+            let prev = if i == 0 { None } else { chars.get(i - 1) };
+            let next = chars.get(i + 1);
+
+            match (prev, next) {
+                (Some(p), Some(n)) if p.is_ascii_digit() && n.is_ascii_digit() => {}
+                _ => return false,
+            }
+        }
+
+        true
+    }
+
+    fn identifier(&mut self) -> Token {
         while self.peek().map_or(false, |c| c.is_alphanumeric()) {
             self.advance();
         }
@@ -239,8 +574,35 @@ impl Scanner {
         let kind = KEYWORDS.get(&text.as_str());
 
         match kind {
-            Some(&k) => self.add_token(k),
-            None => self.add_literal(TokenType::Identifier, Some(Literal::Identifier(text))),
+            Some(&k) => self.make_token(k),
+            None => self.make_literal(TokenType::Identifier, Some(Literal::Identifier(text))),
+        }
+    }
+}
+
+impl Iterator for Scanner {
+    type Item = Token;
+
+    /// Pulls tokens via [`Scanner::next_token`], stopping after `EOF` and
+    /// folding any lexical errors encountered along the way into
+    /// `self.errors` instead of surfacing them through the iterator.
+    fn next(&mut self) -> Option<Token> {
+        loop {
+            if self.exhausted {
+                return None;
+            }
+
+            match self.next_token() {
+                Ok(token) => {
+                    if matches!(token.kind, TokenType::EOF) {
+                        self.exhausted = true;
+                    }
+                    return Some(token);
+                }
+                Err(err) => {
+                    self.errors.push(err);
+                }
+            }
         }
     }
 }
@@ -249,14 +611,295 @@ fn substr(chars: &Vec<char>, start: usize, end: usize) -> String {
     chars[start..end].iter().collect()
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(src: &str) -> Result<Vec<Token>, Vec<ScannerError>> {
+        Scanner::new(src).scan_tokens()
+    }
+
+    #[test]
+    fn unexpected_char_is_reported() {
+        let errors = scan("@").unwrap_err();
+        assert_eq!(
+            errors,
+            vec![ScannerError::UnexpectedChar { line: 1, ch: '@' }]
+        );
+    }
+
+    #[test]
+    fn scanning_continues_past_an_error() {
+        let mut scanner = Scanner::new("@ 1");
+        let tokens: Vec<Token> = scanner.by_ref().collect();
+
+        assert_eq!(
+            scanner.errors,
+            vec![ScannerError::UnexpectedChar { line: 1, ch: '@' }]
+        );
+        assert!(tokens.iter().any(|t| t.kind == TokenType::Number));
+        assert_eq!(tokens.last().unwrap().kind, TokenType::EOF);
+    }
+
+    #[test]
+    fn unterminated_string_is_reported() {
+        let errors = scan("\"abc").unwrap_err();
+        assert_eq!(errors, vec![ScannerError::UnterminatedString { line: 1 }]);
+    }
+
+    #[test]
+    fn next_token_pulls_one_token_at_a_time() {
+        let mut scanner = Scanner::new("1 + 2");
+        assert_eq!(scanner.next_token().unwrap().kind, TokenType::Number);
+        assert_eq!(scanner.next_token().unwrap().kind, TokenType::Plus);
+        assert_eq!(scanner.next_token().unwrap().kind, TokenType::Number);
+        assert_eq!(scanner.next_token().unwrap().kind, TokenType::EOF);
+    }
+
+    #[test]
+    fn iterator_yields_eof_then_stops() {
+        let tokens: Vec<Token> = Scanner::new("1").collect();
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].kind, TokenType::Number);
+        assert_eq!(tokens[1].kind, TokenType::EOF);
+    }
+
+    #[test]
+    fn iterator_does_not_yield_past_eof() {
+        let mut scanner = Scanner::new("");
+        assert_eq!(scanner.next().unwrap().kind, TokenType::EOF);
+        assert!(scanner.next().is_none());
+    }
+
+    #[test]
+    fn token_position_tracks_column_and_span() {
+        let mut scanner = Scanner::new("  +");
+        let token = scanner.next_token().unwrap();
+
+        assert_eq!(token.kind, TokenType::Plus);
+        assert_eq!(token.position.line, 1);
+        assert_eq!(token.position.col_start, 3);
+        assert_eq!(token.position.col_end, 4);
+        assert_eq!(token.position.span_start, 2);
+        assert_eq!(token.position.span_end, 3);
+    }
+
+    #[test]
+    fn multiline_token_reports_its_start_line_not_its_end_line() {
+        // Regression: `position()` used to pair `self.line` (the line the
+        // token ends on) with `start_col` (the column it starts at), so a
+        // token spanning a newline reported a line/column pair that didn't
+        // belong to the same end of the token.
+        let mut scanner = Scanner::new("\"line1\nline2\"");
+        let token = scanner.next_token().unwrap();
+
+        assert_eq!(token.kind, TokenType::String);
+        assert_eq!(token.position.line, 1);
+        assert_eq!(token.position.col_start, 1);
+    }
+
+    #[test]
+    fn decimal_with_digit_group_underscores() {
+        let tokens = scan("1_000_000").unwrap();
+        assert_eq!(tokens[0].literal, Some(Literal::Number(1_000_000.0)));
+    }
+
+    #[test]
+    fn radix_literals_are_decoded() {
+        assert_eq!(
+            scan("0xFF").unwrap()[0].literal,
+            Some(Literal::Number(255.0))
+        );
+        assert_eq!(
+            scan("0b101").unwrap()[0].literal,
+            Some(Literal::Number(5.0))
+        );
+        assert_eq!(
+            scan("0o17").unwrap()[0].literal,
+            Some(Literal::Number(15.0))
+        );
+    }
+
+    #[test]
+    fn full_width_hex_mask_does_not_overflow() {
+        // Regression: radix_number used i64::from_str_radix, which rejects
+        // legitimate 64-bit masks like this one.
+        let tokens = scan("0xFFFFFFFFFFFFFFFF").unwrap();
+        assert_eq!(tokens[0].literal, Some(Literal::Number(u64::MAX as f64)));
+    }
+
+    #[test]
+    fn glued_out_of_range_digit_is_a_single_error() {
+        let errors = scan("0b19").unwrap_err();
+        assert_eq!(
+            errors,
+            vec![ScannerError::InvalidNumber {
+                line: 1,
+                text: "0b19".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn entirely_invalid_radix_suffix_is_swallowed_not_rescanned() {
+        // Regression: when every glued character was out of range (leaving
+        // `digits` empty), the suffix used to be left behind and re-scanned
+        // as its own Identifier token instead of folding into one error.
+        let mut scanner = Scanner::new("0xzz");
+        let tokens: Vec<Token> = scanner.by_ref().collect();
+
+        assert_eq!(
+            scanner.errors,
+            vec![ScannerError::InvalidNumber {
+                line: 1,
+                text: "0xzz".to_string()
+            }]
+        );
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].kind, TokenType::EOF);
+    }
+
+    #[test]
+    fn underscore_touching_the_decimal_point_is_rejected() {
+        let errors = scan("1_.5").unwrap_err();
+        assert!(matches!(errors[0], ScannerError::InvalidNumber { .. }));
+    }
+
+    #[test]
+    fn trailing_underscore_at_eof_is_rejected() {
+        let errors = scan("1_").unwrap_err();
+        assert!(matches!(errors[0], ScannerError::InvalidNumber { .. }));
+    }
+
+    #[test]
+    fn block_comment_is_skipped() {
+        let tokens = scan("/* comment */ 1").unwrap();
+        assert_eq!(tokens[0].kind, TokenType::Number);
+    }
+
+    #[test]
+    fn nested_block_comments_are_tracked_by_depth() {
+        let tokens = scan("/* outer /* inner */ still outer */ 1").unwrap();
+        assert_eq!(tokens[0].kind, TokenType::Number);
+    }
+
+    #[test]
+    fn block_comment_closing_at_end_of_input() {
+        // Regression: peek_next's off-by-one used to miss a closing `*/`
+        // when it was the very last thing in the source.
+        let tokens = scan("/**/").unwrap();
+        assert_eq!(tokens[0].kind, TokenType::EOF);
+    }
+
+    #[test]
+    fn unterminated_block_comment_reports_its_opening_line() {
+        let errors = scan("/* never closed").unwrap_err();
+        assert_eq!(errors, vec![ScannerError::UnterminatedComment { line: 1 }]);
+    }
+
+    #[test]
+    fn line_comment_with_no_trailing_newline_does_not_panic() {
+        // Regression: the scan loop used to index past the end of the
+        // source when a `//` comment ran all the way to EOF.
+        let tokens = scan("// trailing comment, no newline").unwrap();
+        assert_eq!(tokens[0].kind, TokenType::EOF);
+    }
+
+    #[test]
+    fn decodes_common_escapes() {
+        let tokens = scan("\"a\\nb\\tc\\r\\\\d\\\"e\\0f\"").unwrap();
+        assert_eq!(
+            tokens[0].literal,
+            Some(Literal::String("a\nb\tc\r\\d\"e\0f".to_string()))
+        );
+    }
+
+    #[test]
+    fn decodes_unicode_escape() {
+        let tokens = scan("\"\\u{1F600}\"").unwrap();
+        assert_eq!(
+            tokens[0].literal,
+            Some(Literal::String("\u{1F600}".to_string()))
+        );
+    }
+
+    #[test]
+    fn invalid_escape_is_reported_through_next_token() {
+        // Regression: InvalidEscape used to be pushed straight onto the
+        // private `self.errors` side-channel, invisible to a caller driving
+        // the scanner one token at a time via `next_token` directly.
+        let mut scanner = Scanner::new("\"\\q oops\"");
+        let err = scanner.next_token().unwrap_err();
+        assert_eq!(
+            err,
+            ScannerError::InvalidEscape {
+                line: 1,
+                seq: "q".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn scanning_resumes_right_after_a_string_with_an_invalid_escape() {
+        // A bad escape shouldn't desync the rest of the scan: the string is
+        // fully consumed up to its real closing quote before the error is
+        // reported, so the next call to `next_token` picks up cleanly.
+        let mut scanner = Scanner::new("print \"\\q oops\"; print \"next\";");
+        let mut kinds = Vec::new();
+
+        loop {
+            match scanner.next_token() {
+                Ok(token) => {
+                    let done = token.kind == TokenType::EOF;
+                    kinds.push(token.kind);
+                    if done {
+                        break;
+                    }
+                }
+                Err(_) => {}
+            }
+        }
+
+        assert_eq!(
+            kinds,
+            vec![
+                TokenType::Print,
+                TokenType::Semicolon,
+                TokenType::Print,
+                TokenType::String,
+                TokenType::Semicolon,
+                TokenType::EOF,
+            ]
+        );
+    }
+
+    #[test]
+    fn backslash_as_the_last_character_is_an_unterminated_string() {
+        let errors = scan("\"abc\\").unwrap_err();
+        assert_eq!(errors, vec![ScannerError::UnterminatedString { line: 1 }]);
+    }
+}
+
 pub fn run(source: &str) -> std::io::Result<()> {
     let mut scanner = Scanner::new(source);
 
-    let tokens = scanner.scan_tokens();
+    match scanner.scan_tokens() {
+        Ok(tokens) => {
+            for token in tokens {
+                println!("{}", token)
+            }
 
-    for token in tokens {
-        println!("{}", token)
-    }
+            Ok(())
+        }
+        Err(errors) => {
+            for error in &errors {
+                eprintln!("{}", error);
+            }
 
-    Ok(())
+            Err(Error::new(
+                ErrorKind::Other,
+                format!("{} lexical error(s)", errors.len()),
+            ))
+        }
+    }
 }
@@ -0,0 +1,116 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenType {
+    // Single-character tokens
+    LeftParen,
+    RightParen,
+    LeftBrace,
+    RightBrace,
+    Comma,
+    Dot,
+    Minus,
+    Plus,
+    Semicolon,
+    Slash,
+    Star,
+
+    // One or two character tokens
+    Bang,
+    BangEqual,
+    Equal,
+    EqualEqual,
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+
+    // Literals
+    Identifier,
+    String,
+    Number,
+
+    // Keywords
+    And,
+    Class,
+    Else,
+    False,
+    Fun,
+    For,
+    If,
+    Nil,
+    Or,
+    Print,
+    Return,
+    Super,
+    This,
+    True,
+    Var,
+    While,
+
+    EOF,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    String(String),
+    Number(f64),
+    Identifier(String),
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Literal::String(s) => write!(f, "{}", s),
+            Literal::Number(n) => write!(f, "{}", n),
+            Literal::Identifier(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Where a token sits in the source: its line, the column range it spans on
+/// that line, and the char offsets into the source needed for precise
+/// underlining. `line` and `col_start` refer to the token's first char;
+/// `col_end` is the column just past its last char, so for a string literal
+/// that spans several lines it is a column on the token's last line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    pub line: u32,
+    pub col_start: u32,
+    pub col_end: u32,
+    pub span_start: usize,
+    pub span_end: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenType,
+    pub lexeme: String,
+    pub literal: Option<Literal>,
+    pub position: Position,
+}
+
+impl Token {
+    pub fn new(
+        kind: TokenType,
+        lexeme: String,
+        literal: Option<Literal>,
+        position: Position,
+    ) -> Token {
+        Token {
+            kind,
+            lexeme,
+            literal,
+            position,
+        }
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.literal {
+            Some(literal) => write!(f, "{:?} {} {}", self.kind, self.lexeme, literal),
+            None => write!(f, "{:?} {}", self.kind, self.lexeme),
+        }
+    }
+}